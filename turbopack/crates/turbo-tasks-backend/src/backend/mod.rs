@@ -818,8 +818,10 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
     }
 
     fn get_task_description(&self, task: TaskId) -> std::string::String {
-        let task_type = self.lookup_task_type(task).expect("Task not found");
-        task_type.to_string()
+        self.lookup_task_type(task).map_or_else(
+            || format!("[{task}] transient"),
+            |task_type| format!("[{task}] {task_type}"),
+        )
     }
 
     fn try_get_function_id(&self, task_id: TaskId) -> Option<FunctionId> {
@@ -93,8 +93,12 @@ impl UpdateOutputOperation {
                 OutputValue::Error
             }
             Err(panic) => {
+                let error = match panic {
+                    Some(message) => anyhow!("A task panicked: {message}"),
+                    None => anyhow!("A task panicked"),
+                };
                 task.insert(CachedDataItem::Error {
-                    value: SharedError::new(anyhow!("Panic: {:?}", panic)),
+                    value: SharedError::new(error),
                 });
                 OutputValue::Panic
             }
@@ -95,6 +95,8 @@ pub fn generate_register() {
         // TODO hash src dir
         let hash = "TODO";
 
+        // All targets share the prefix, so root level items of `lib.rs` and of a
+        // test, example or bench file can't have the same name
         let prefix = format!("{crate_name}@{hash}::");
 
         let mut register_code = String::new();
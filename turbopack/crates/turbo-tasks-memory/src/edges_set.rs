@@ -122,6 +122,12 @@ impl EdgesDataEntry {
         }
     }
 
+    /// Returns true if the entry contains any edge other than a child edge,
+    /// i.e. a read of the task's output, cells or collectibles.
+    fn has_dependency(&self) -> bool {
+        self.iter().any(|edge| edge != EdgeEntry::Child)
+    }
+
     fn iter(&self) -> impl Iterator<Item = EdgeEntry> + '_ {
         match self {
             EdgesDataEntry::Empty => unreachable!(),
@@ -525,6 +531,13 @@ impl TaskEdgesSet {
             _ => None,
         })
     }
+
+    /// Returns the tasks that are read by the owner of this set.
+    pub fn dependencies(&self) -> impl Iterator<Item = TaskId> + '_ {
+        self.edges
+            .iter()
+            .filter_map(|(task, entry)| entry.has_dependency().then_some(*task))
+    }
 }
 
 impl IntoIterator for TaskEdgesSet {
@@ -570,6 +583,13 @@ impl TaskEdgesList {
             _ => None,
         })
     }
+
+    /// Returns the tasks that are read by the owner of this list.
+    pub fn dependencies(&self) -> impl Iterator<Item = TaskId> + '_ {
+        self.edges
+            .iter()
+            .filter_map(|(task, entry)| entry.has_dependency().then_some(*task))
+    }
 }
 
 impl IntoIterator for TaskEdgesList {
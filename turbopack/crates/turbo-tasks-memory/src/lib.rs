@@ -15,6 +15,8 @@ mod memory_backend;
 mod output;
 mod task;
 mod task_statistics;
+mod viz;
 
 pub use memory_backend::MemoryBackend;
-pub use task_statistics::{TaskStatistics, TaskStatisticsApi};
+pub use task_statistics::{TaskFunctionStatistics, TaskStatistics, TaskStatisticsApi};
+pub use viz::{TaskGraph, TaskGraphDiff, TaskGraphNode};
@@ -1,11 +1,12 @@
 use std::{
     borrow::{Borrow, Cow},
+    cmp::Reverse,
     future::Future,
     hash::{BuildHasher, BuildHasherDefault, Hash},
-    num::NonZeroU32,
+    num::{NonZeroU32, NonZeroU64},
     pin::Pin,
     sync::{
-        atomic::{AtomicBool, AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
     time::Duration,
@@ -13,7 +14,7 @@ use std::{
 
 use anyhow::{anyhow, bail, Result};
 use auto_hash_map::AutoMap;
-use dashmap::{mapref::entry::Entry, DashMap};
+use dashmap::{mapref::entry::Entry, DashMap, DashSet};
 use rustc_hash::FxHasher;
 use tracing::trace_span;
 use turbo_prehash::{BuildHasherExt, PassThroughHash, PreHashed};
@@ -37,6 +38,7 @@ use crate::{
     output::Output,
     task::{ReadCellError, Task, TaskType},
     task_statistics::TaskStatisticsApi,
+    viz::TaskGraph,
 };
 
 fn prehash_task_type(task_type: CachedTaskType) -> PreHashed<CachedTaskType> {
@@ -62,6 +64,10 @@ pub struct MemoryBackend {
     gc_queue: Option<GcQueue>,
     idle_gc_active: AtomicBool,
     task_statistics: TaskStatisticsApi,
+    /// Root tasks that have been created and not disposed yet.
+    root_tasks: DashSet<TaskId, BuildHasherDefault<FxHasher>>,
+    /// The number of task executions completed so far.
+    executions: AtomicU64,
 }
 
 impl Default for MemoryBackend {
@@ -88,9 +94,18 @@ impl MemoryBackend {
             gc_queue: (memory_limit != usize::MAX).then(GcQueue::new),
             idle_gc_active: AtomicBool::new(false),
             task_statistics: TaskStatisticsApi::default(),
+            root_tasks: DashSet::default(),
+            executions: AtomicU64::new(0),
         }
     }
 
+    /// Returns a new id for a completed task execution. Ids are unique for the
+    /// lifetime of the backend, so they tell executions of the same task apart
+    /// even when it was unloaded in between.
+    pub(crate) fn next_execution_id(&self) -> NonZeroU64 {
+        NonZeroU64::new(self.executions.fetch_add(1, Ordering::Relaxed) + 1).unwrap()
+    }
+
     fn connect_task_child(
         &self,
         parent: TaskId,
@@ -130,14 +145,39 @@ impl MemoryBackend {
     }
 
     pub fn with_all_cached_tasks(&self, mut func: impl FnMut(TaskId)) {
-        for id in self.task_cache.clone().into_read_only().values() {
-            func(*id);
-        }
-        for id in self.transient_task_cache.clone().into_read_only().values() {
-            func(*id);
+        // Only the ids are copied out, so `func` can run without holding any
+        // locks on the caches.
+        let ids: Vec<TaskId> = self
+            .task_cache
+            .iter()
+            .chain(self.transient_task_cache.iter())
+            .map(|entry| *entry.value())
+            .collect();
+        for id in ids {
+            func(id);
         }
     }
 
+    /// Invalidates all cached tasks of the given function, e.g. after its
+    /// implementation was reloaded. Active tasks are scheduled for
+    /// re-execution, and any change of their output propagates to their
+    /// dependents as usual. Returns the number of invalidated tasks.
+    pub fn invalidate_function(
+        &self,
+        function: FunctionId,
+        turbo_tasks: &dyn TurboTasksBackendApi<MemoryBackend>,
+    ) -> usize {
+        let tasks: Vec<TaskId> = self
+            .task_cache
+            .iter()
+            .chain(self.transient_task_cache.iter())
+            .filter(|entry| entry.key().try_get_function_id() == Some(function))
+            .map(|entry| *entry.value())
+            .collect();
+        self.invalidate_tasks(&tasks, turbo_tasks);
+        tasks.len()
+    }
+
     #[inline(always)]
     pub fn with_task<T>(&self, id: TaskId, func: impl FnOnce(&Task) -> T) -> T {
         let value = *id;
@@ -323,6 +363,44 @@ impl MemoryBackend {
         }
     }
 
+    /// Returns the ids of all root tasks that haven't been disposed yet, sorted
+    /// by id. Root tasks are not cached, so they are not visited by
+    /// [`MemoryBackend::with_all_cached_tasks`].
+    pub fn root_tasks(&self) -> Vec<TaskId> {
+        let mut ids: Vec<TaskId> = self.root_tasks.iter().map(|id| *id).collect();
+        ids.sort();
+        ids
+    }
+
+    /// Collects a [`TaskGraph`] of all cached and root tasks. This is intended
+    /// for debugging, e.g. by rendering it with [`TaskGraph::to_dot`].
+    pub fn task_graph(&self) -> TaskGraph {
+        TaskGraph::new(self)
+    }
+
+    /// Returns up to `n` cached tasks with the longest last execution, slowest
+    /// first, together with their function names. The duration is the time
+    /// spent polling the task, so time waiting for other tasks isn't included.
+    /// Tasks that haven't completed an execution yet, or were unloaded by
+    /// garbage collection since, are skipped.
+    pub fn slowest_tasks(&self, n: usize) -> Vec<(TaskId, Cow<'static, str>, Duration)> {
+        let mut tasks = Vec::new();
+        self.with_all_cached_tasks(|id| {
+            if let Some(duration) = self.with_task(id, |task| task.last_duration()) {
+                tasks.push((id, duration));
+            }
+        });
+        tasks.sort_by_key(|&(id, duration)| (Reverse(duration), id));
+        tasks.truncate(n);
+        tasks
+            .into_iter()
+            .filter_map(|(id, duration)| {
+                let name = self.with_task(id, |task| task.get_function_name())?;
+                Some((id, name, duration))
+            })
+            .collect()
+    }
+
     pub fn task_statistics(&self) -> &TaskStatisticsApi {
         &self.task_statistics
     }
@@ -378,6 +456,26 @@ impl MemoryBackend {
         });
     }
 
+    fn track_execution(&self, task_id: TaskId, duration: Duration) {
+        self.task_statistics().map(|stats| {
+            self.with_task(task_id, |task| match &task.ty {
+                TaskType::Persistent { ty } | TaskType::Transient { ty } => {
+                    if let CachedTaskType::Native {
+                        fn_type: function_id,
+                        this: _,
+                        arg: _,
+                    } = &***ty
+                    {
+                        stats.record_execution(*function_id, duration);
+                    }
+                    // `ResolveNative` and `ResolveTrait` re-execute themselves as `Native`
+                    // after resolving their arguments, like in `track_cache_miss`
+                }
+                TaskType::Root(..) | TaskType::Once(..) => {}
+            })
+        });
+    }
+
     fn track_cache_miss(&self, task_type: &PreHashed<CachedTaskType>) {
         self.task_statistics().map(|stats| match &**task_type {
             CachedTaskType::Native {
@@ -482,6 +580,7 @@ impl Backend for MemoryBackend {
         stateful: bool,
         turbo_tasks: &dyn TurboTasksBackendApi<MemoryBackend>,
     ) -> bool {
+        self.track_execution(task_id, duration);
         let generation = if let Some(gc_queue) = &self.gc_queue {
             gc_queue.generation()
         } else {
@@ -810,6 +909,7 @@ impl Backend for MemoryBackend {
                 let task = Task::new_root(id, move || f() as _);
                 // SAFETY: We have a fresh task id where nobody knows about yet
                 unsafe { self.transient_tasks.insert(index, task) };
+                self.root_tasks.insert(id);
                 Task::set_root(id, self, turbo_tasks);
             }
             TransientTaskType::Once(f) => {
@@ -823,6 +923,7 @@ impl Backend for MemoryBackend {
     }
 
     fn dispose_root_task(&self, task: TaskId, turbo_tasks: &dyn TurboTasksBackendApi<Self>) {
+        self.root_tasks.remove(&task);
         Task::unset_root(task, self, turbo_tasks);
     }
 }
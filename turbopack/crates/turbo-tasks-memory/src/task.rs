@@ -4,7 +4,7 @@ use std::{
     future::Future,
     hash::{BuildHasherDefault, Hash},
     mem::{replace, take},
-    num::NonZeroU32,
+    num::{NonZeroU32, NonZeroU64},
     pin::Pin,
     sync::{atomic::AtomicU32, Arc},
     time::Duration,
@@ -34,6 +34,7 @@ use crate::{
     gc::{GcQueue, GcTaskState},
     output::Output,
     task::aggregation::{TaskAggregationContext, TaskChange},
+    viz::TaskGraphNode,
     MemoryBackend,
 };
 
@@ -91,8 +92,9 @@ impl Debug for TaskType {
         match self {
             Self::Root(..) => f.debug_tuple("Root").finish(),
             Self::Once(..) => f.debug_tuple("Once").finish(),
-            Self::Persistent { ty, .. } => Debug::fmt(ty, f),
-            Self::Transient { ty } => Debug::fmt(ty, f),
+            // skip the `PreHashed` wrapper, the hash isn't useful for debugging
+            Self::Persistent { ty, .. } => Debug::fmt(&***ty, f),
+            Self::Transient { ty } => Debug::fmt(&***ty, f),
         }
     }
 }
@@ -160,6 +162,13 @@ struct TaskState {
 
     // GC state:
     gc: GcTaskState,
+
+    /// The duration of the last completed execution in microseconds, saturated
+    /// at `u32::MAX`. Only meaningful when `last_execution` is set.
+    last_duration_micros: u32,
+    /// The id of the last completed execution, see
+    /// [`MemoryBackend::next_execution_id`].
+    last_execution: Option<NonZeroU64>,
 }
 
 impl TaskState {
@@ -173,6 +182,8 @@ impl TaskState {
             output: Default::default(),
             cells: Default::default(),
             gc: Default::default(),
+            last_duration_micros: 0,
+            last_execution: None,
         }
     }
 
@@ -194,6 +205,8 @@ impl TaskState {
             output: Default::default(),
             cells: Default::default(),
             gc: Default::default(),
+            last_duration_micros: 0,
+            last_execution: None,
         }
     }
 }
@@ -218,6 +231,8 @@ impl PartialTaskState {
             output: Default::default(),
             cells: Default::default(),
             gc: Default::default(),
+            last_duration_micros: 0,
+            last_execution: None,
         }
     }
 }
@@ -245,6 +260,8 @@ impl UnloadedTaskState {
             output: Default::default(),
             cells: Default::default(),
             gc: Default::default(),
+            last_duration_micros: 0,
+            last_execution: None,
         }
     }
 
@@ -417,6 +434,21 @@ impl TaskStateType {
         }
     }
 
+    /// The tasks read by the last execution. For a task in progress, only the reads of the
+    /// previous execution are known.
+    fn dependencies(&self) -> impl Iterator<Item = TaskId> + '_ {
+        match self {
+            TaskStateType::Done { edges, .. } => Either::Left(edges.dependencies()),
+            TaskStateType::InProgress(box InProgressState { outdated_edges, .. }) => {
+                Either::Right(outdated_edges.dependencies())
+            }
+            TaskStateType::Dirty { outdated_edges, .. }
+            | TaskStateType::Scheduled(box ScheduledState { outdated_edges, .. }) => {
+                Either::Right(outdated_edges.dependencies())
+            }
+        }
+    }
+
     fn into_dependencies_and_children(self) -> (TaskEdgesSet, SmallVec<[TaskId; 6]>) {
         match self {
             TaskStateType::Done { edges, .. } => {
@@ -910,12 +942,7 @@ impl Task {
                         state.output.link(result, turbo_tasks)
                     }
                 }
-                Ok(Err(mut err)) => {
-                    if let Some(name) = self.get_function_name() {
-                        err = err.context(format!("Execution of {} failed", name));
-                    }
-                    state.output.error(err, turbo_tasks)
-                }
+                Ok(Err(err)) => state.output.error(err, turbo_tasks),
                 Err(message) => state.output.panic(message, turbo_tasks),
             },
 
@@ -953,6 +980,8 @@ impl Task {
                 state
                     .gc
                     .execution_completed(duration, memory_usage, generation);
+                state.last_duration_micros = duration.as_micros().try_into().unwrap_or(u32::MAX);
+                state.last_execution = Some(backend.next_execution_id());
 
                 let TaskState {
                     ref mut cells,
@@ -1526,6 +1555,33 @@ impl Task {
         }
     }
 
+    /// Returns a [`TaskGraphNode`] describing the current state and output of
+    /// the task, its children and the tasks it read from.
+    pub(crate) fn graph_node(&self) -> TaskGraphNode {
+        let mut node = TaskGraphNode {
+            id: self.id,
+            name: self.ty.to_string(),
+            args: format!("{:?}", self.ty),
+            state: "unloaded",
+            output: None,
+            execution: None,
+            children: Vec::new(),
+            dependencies: Vec::new(),
+        };
+        match self.state() {
+            TaskMetaStateReadGuard::Full(state) => {
+                node.state = Task::state_string(&state);
+                node.output = state.output.content.as_ref().map(ToString::to_string);
+                node.execution = state.last_execution.map(NonZeroU64::get);
+                node.children = state.state_type.children().collect();
+                node.dependencies = state.state_type.dependencies().collect();
+            }
+            TaskMetaStateReadGuard::Partial(_) => node.state = "partial",
+            TaskMetaStateReadGuard::Unloaded => {}
+        }
+        node
+    }
+
     fn state_string(state: &TaskState) -> &'static str {
         match state.state_type {
             Scheduled { .. } => "scheduled",
@@ -1830,6 +1886,18 @@ impl Task {
         }
     }
 
+    /// Returns the duration of the last completed execution, if the task has
+    /// been executed and wasn't unloaded since.
+    pub(crate) fn last_duration(&self) -> Option<Duration> {
+        if let TaskMetaStateReadGuard::Full(state) = self.state() {
+            state
+                .last_execution
+                .map(|_| Duration::from_micros(state.last_duration_micros.into()))
+        } else {
+            None
+        }
+    }
+
     fn unload(
         &self,
         mut full_state: FullTaskWriteGuard<'_>,
@@ -1880,6 +1948,8 @@ impl Task {
             state_type,
             // can be dropped as only gc meta info
             gc: _,
+            last_duration_micros: _,
+            last_execution: _,
         } = old_state.into_full().unwrap();
 
         let (dependencies, children) = state_type.into_dependencies_and_children();
@@ -1,6 +1,7 @@
 use std::{
     hash::BuildHasherDefault,
     sync::{Arc, OnceLock},
+    time::Duration,
 };
 
 use dashmap::DashMap;
@@ -55,6 +56,30 @@ impl TaskStatistics {
         self.with_task_type_statistics(function_id, |stats| stats.cache_miss += 1)
     }
 
+    pub(crate) fn record_execution(&self, function_id: FunctionId, duration: Duration) {
+        self.with_task_type_statistics(function_id, |stats| {
+            stats.executions += 1;
+            stats.duration += duration;
+        })
+    }
+
+    /// Returns the statistics of every function by its global name, sorted by
+    /// the total time spent executing it, slowest first.
+    pub fn by_duration(&self) -> Vec<(&'static str, TaskFunctionStatistics)> {
+        let mut stats: Vec<_> = self
+            .inner
+            .iter()
+            .map(|entry| {
+                (
+                    registry::get_function_global_name(*entry.key()),
+                    *entry.value(),
+                )
+            })
+            .collect();
+        stats.sort_by(|(_, a), (_, b)| b.duration.cmp(&a.duration));
+        stats
+    }
+
     fn with_task_type_statistics(
         &self,
         task_function_id: FunctionId,
@@ -65,10 +90,23 @@ impl TaskStatistics {
 }
 
 /// Statistics for an individual function.
-#[derive(Default, Serialize)]
-struct TaskFunctionStatistics {
-    cache_hit: u32,
-    cache_miss: u32,
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct TaskFunctionStatistics {
+    pub cache_hit: u32,
+    pub cache_miss: u32,
+    /// The number of times the function was executed, including
+    /// re-executions after invalidation.
+    pub executions: u32,
+    /// The total time spent executing the function.
+    #[serde(rename = "duration_us", serialize_with = "serialize_micros")]
+    pub duration: Duration,
+}
+
+fn serialize_micros<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_u64(duration.as_micros().try_into().unwrap_or(u64::MAX))
 }
 
 impl Serialize for TaskStatistics {
@@ -0,0 +1,276 @@
+use std::fmt::Write;
+
+use rustc_hash::FxHashMap;
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use turbo_tasks::{TaskId, TaskIdSet};
+
+use crate::MemoryBackend;
+
+/// A snapshot of the cached and root tasks of a [`MemoryBackend`] with their
+/// states and outputs, the parent-child relationships between them and the
+/// tasks each task read from.
+///
+/// The snapshot owns its data and holds no locks, so it can be kept around and
+/// inspected while the backend keeps running. Since tasks are copied one at a
+/// time while the graph may change concurrently, it is only best-effort
+/// consistent. But every edge points to a task that is part of the snapshot.
+///
+/// Tasks and their edges are sorted by [`TaskId`], so the same graph always
+/// renders and serializes the same way.
+#[derive(Clone, Debug)]
+pub struct TaskGraph {
+    pub tasks: Vec<TaskGraphNode>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TaskGraphNode {
+    pub id: TaskId,
+    pub name: String,
+    /// The `Debug` representation of the function, `self` and arguments the
+    /// task was called with. Root and once tasks have no inputs, so this is
+    /// just `Root` or `Once` for them.
+    pub args: String,
+    pub state: &'static str,
+    /// The output of the task, e.g. a link to the cell holding its value or
+    /// the error it failed with. `None` if the task has no output yet or
+    /// wasn't fully loaded.
+    pub output: Option<String>,
+    /// Identifies the last completed execution of the task. It changes every
+    /// time the task is executed again, even when its state and output end up
+    /// the same. `None` if the task hasn't completed an execution yet or
+    /// wasn't fully loaded.
+    pub execution: Option<u64>,
+    #[serde(skip)]
+    pub children: Vec<TaskId>,
+    /// Tasks whose output, cells or collectibles were read by this task. Reads
+    /// are tracked per task, not per cell. For a task in progress, these are
+    /// the reads of its previous execution.
+    #[serde(skip)]
+    pub dependencies: Vec<TaskId>,
+}
+
+impl TaskGraph {
+    pub fn new(backend: &MemoryBackend) -> Self {
+        let mut tasks = Vec::new();
+        let mut add_task = |id| {
+            let mut node = backend.with_task(id, |task| task.graph_node());
+            // An in progress task can list a child both as outdated and as new
+            node.children.sort();
+            node.children.dedup();
+            node.dependencies.sort();
+            tasks.push(node);
+        };
+        backend.with_all_cached_tasks(&mut add_task);
+        backend.root_tasks().into_iter().for_each(add_task);
+        tasks.sort_by_key(|node| node.id);
+        // Tasks that aren't cached (e.g. once tasks) are not part of the graph,
+        // so drop edges to them to keep every edge endpoint in the graph.
+        let ids: TaskIdSet = tasks.iter().map(|node| node.id).collect();
+        for node in &mut tasks {
+            node.children.retain(|child| ids.contains(child));
+            node.dependencies
+                .retain(|dependency| ids.contains(dependency));
+        }
+        Self { tasks }
+    }
+
+    /// Renders the graph in the Graphviz DOT format, e.g. to be piped into
+    /// `dot -Tsvg`. Nodes are labeled with the task name and id and colored by
+    /// their state. Solid edges point from a task to the tasks it called, dashed
+    /// edges to the tasks it read from.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph {\n");
+        for node in &self.tasks {
+            let id = *node.id;
+            writeln!(
+                out,
+                "  {id} [label=\"{} [{id}]\\n{}\", style=filled, fillcolor={}];",
+                escape_label(&node.name),
+                node.state,
+                state_color(node.state),
+            )
+            .unwrap();
+        }
+        for node in &self.tasks {
+            for child in &node.children {
+                writeln!(out, "  {} -> {};", *node.id, **child).unwrap();
+            }
+            for dependency in &node.dependencies {
+                writeln!(out, "  {} -> {} [style=dashed];", *node.id, **dependency).unwrap();
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Compares this snapshot with a `newer` snapshot of the same backend.
+    /// Tasks are matched by id, as the backend never hands out the id of a task
+    /// that was part of a snapshot to a different task.
+    pub fn diff(&self, newer: &TaskGraph) -> TaskGraphDiff {
+        let old_ids: TaskIdSet = self.tasks.iter().map(|node| node.id).collect();
+        let new_tasks: FxHashMap<TaskId, &TaskGraphNode> =
+            newer.tasks.iter().map(|node| (node.id, node)).collect();
+
+        let mut diff = TaskGraphDiff::default();
+        for old in &self.tasks {
+            let (children, dependencies) = match new_tasks.get(&old.id) {
+                Some(new) => {
+                    if old.execution != new.execution
+                        || old.state != new.state
+                        || old.output != new.output
+                    {
+                        diff.changed.push(old.id);
+                    }
+                    (&new.children[..], &new.dependencies[..])
+                }
+                None => {
+                    diff.removed.push(old.id);
+                    (&[][..], &[][..])
+                }
+            };
+            diff_edges(
+                old.id,
+                &old.children,
+                children,
+                &mut diff.added_edges,
+                &mut diff.removed_edges,
+            );
+            diff_edges(
+                old.id,
+                &old.dependencies,
+                dependencies,
+                &mut diff.added_dependencies,
+                &mut diff.removed_dependencies,
+            );
+        }
+        for new in &newer.tasks {
+            if !old_ids.contains(&new.id) {
+                diff.added.push(new.id);
+                diff_edges(
+                    new.id,
+                    &[],
+                    &new.children,
+                    &mut diff.added_edges,
+                    &mut diff.removed_edges,
+                );
+                diff_edges(
+                    new.id,
+                    &[],
+                    &new.dependencies,
+                    &mut diff.added_dependencies,
+                    &mut diff.removed_dependencies,
+                );
+            }
+        }
+        diff
+    }
+}
+
+/// Adds the edges from `from` that are only in `new` to `added` and the ones
+/// that are only in `old` to `removed`. Both lists have to be sorted.
+fn diff_edges(
+    from: TaskId,
+    old: &[TaskId],
+    new: &[TaskId],
+    added: &mut Vec<(TaskId, TaskId)>,
+    removed: &mut Vec<(TaskId, TaskId)>,
+) {
+    removed.extend(
+        old.iter()
+            .filter(|to| new.binary_search(to).is_err())
+            .map(|&to| (from, to)),
+    );
+    added.extend(
+        new.iter()
+            .filter(|to| old.binary_search(to).is_err())
+            .map(|&to| (from, to)),
+    );
+}
+
+/// The difference between two [`TaskGraph`] snapshots, see [`TaskGraph::diff`].
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct TaskGraphDiff {
+    /// Tasks that only exist in the newer snapshot.
+    pub added: Vec<TaskId>,
+    /// Tasks that only exist in the older snapshot.
+    pub removed: Vec<TaskId>,
+    /// Tasks that exist in both snapshots, but were executed again in between
+    /// or changed their state or output, e.g. because they were invalidated.
+    pub changed: Vec<TaskId>,
+    /// Child edges that only exist in the newer snapshot.
+    pub added_edges: Vec<(TaskId, TaskId)>,
+    /// Child edges that only exist in the older snapshot.
+    pub removed_edges: Vec<(TaskId, TaskId)>,
+    /// Dependency edges that only exist in the newer snapshot.
+    pub added_dependencies: Vec<(TaskId, TaskId)>,
+    /// Dependency edges that only exist in the older snapshot.
+    pub removed_dependencies: Vec<(TaskId, TaskId)>,
+}
+
+impl TaskGraphDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.added_dependencies.is_empty()
+            && self.removed_dependencies.is_empty()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+enum TaskGraphEdgeKind {
+    Child,
+    Dependency,
+}
+
+#[derive(Serialize)]
+struct TaskGraphEdge {
+    from: TaskId,
+    to: TaskId,
+    kind: TaskGraphEdgeKind,
+}
+
+/// Serializes as `{ tasks: [...], edges: [...] }`, where each edge references
+/// the ids of two tasks and is either a `"child"` or a `"dependency"` edge.
+impl Serialize for TaskGraph {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let edges: Vec<_> = self
+            .tasks
+            .iter()
+            .flat_map(|node| {
+                let children = node.children.iter().map(|&to| TaskGraphEdge {
+                    from: node.id,
+                    to,
+                    kind: TaskGraphEdgeKind::Child,
+                });
+                let dependencies = node.dependencies.iter().map(|&to| TaskGraphEdge {
+                    from: node.id,
+                    to,
+                    kind: TaskGraphEdgeKind::Dependency,
+                });
+                children.chain(dependencies)
+            })
+            .collect();
+        let mut graph = serializer.serialize_struct("TaskGraph", 2)?;
+        graph.serialize_field("tasks", &self.tasks)?;
+        graph.serialize_field("edges", &edges)?;
+        graph.end()
+    }
+}
+
+fn state_color(state: &str) -> &'static str {
+    match state {
+        "done" => "lightgreen",
+        "scheduled" => "lightyellow",
+        "dirty" => "lightcoral",
+        s if s.starts_with("in progress") => "lightblue",
+        _ => "lightgray",
+    }
+}
+
+fn escape_label(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
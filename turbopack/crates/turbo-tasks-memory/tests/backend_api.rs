@@ -0,0 +1,279 @@
+#![feature(arbitrary_self_types)]
+#![feature(arbitrary_self_types_pointers)]
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use anyhow::Result;
+use serde_json::json;
+use turbo_tasks::{backend::Backend, ReadConsistency, TurboTasks, Vc};
+use turbo_tasks_memory::MemoryBackend;
+use turbo_tasks_testing::{register, Registration};
+
+static REGISTRATION: Registration = register!();
+
+static COUNTED_EXECUTIONS: AtomicU32 = AtomicU32::new(0);
+
+#[tokio::test]
+async fn test_dot_output() {
+    run_with_tt(|tt| async move {
+        assert_eq!(*outer(3).await.unwrap(), 7);
+        let graph = tt.backend().task_graph();
+        let outer = graph
+            .tasks
+            .iter()
+            .find(|node| node.name.ends_with("outer"))
+            .expect("outer task should be in the graph");
+        let inner = graph
+            .tasks
+            .iter()
+            .find(|node| node.name.ends_with("inner"))
+            .expect("inner task should be in the graph");
+        assert_eq!(outer.state, "done");
+        assert_eq!(inner.state, "done");
+        let output = inner.output.as_deref().unwrap();
+        assert!(output.starts_with("link "), "{output}");
+        assert!(outer.children.contains(&inner.id));
+        assert!(outer.dependencies.contains(&inner.id));
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph {\n"), "{dot}");
+        assert!(dot.ends_with("}\n"), "{dot}");
+        assert!(
+            dot.contains(&format!("  {} -> {};", *outer.id, *inner.id)),
+            "{dot}"
+        );
+        assert!(
+            dot.contains(&format!("  {} -> {} [style=dashed];", *outer.id, *inner.id)),
+            "{dot}"
+        );
+        assert!(dot.contains("fillcolor=lightgreen"), "{dot}");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_json_output() {
+    run_with_tt(|tt| async move {
+        assert_eq!(*outer(5).await.unwrap(), 11);
+        let json = serde_json::to_value(tt.backend().task_graph()).unwrap();
+        let tasks = json["tasks"].as_array().unwrap();
+        let ids: Vec<_> = tasks
+            .iter()
+            .map(|task| task["id"].as_u64().unwrap())
+            .collect();
+        let mut sorted_ids = ids.clone();
+        sorted_ids.sort();
+        assert_eq!(ids, sorted_ids);
+
+        let id_of = |suffix: &str| {
+            tasks
+                .iter()
+                .find(|task| task["name"].as_str().unwrap().ends_with(suffix))
+                .unwrap()["id"]
+                .clone()
+        };
+        let edges = json["edges"].as_array().unwrap();
+        for kind in ["child", "dependency"] {
+            let edge = json!({ "from": id_of("outer"), "to": id_of("inner"), "kind": kind });
+            assert!(edges.contains(&edge), "{json}");
+        }
+
+        // serializing the same graph twice yields the same output
+        assert_eq!(
+            json,
+            serde_json::to_value(tt.backend().task_graph()).unwrap()
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_diff() {
+    run_with_tt(|tt| async move {
+        assert_eq!(*outer(1).await.unwrap(), 3);
+        let before = tt.backend().task_graph();
+        assert!(before.diff(&before).is_empty());
+
+        assert_eq!(*outer(2).await.unwrap(), 5);
+        let after = tt.backend().task_graph();
+        let outers: Vec<_> = after
+            .tasks
+            .iter()
+            .filter(|node| node.name.ends_with("outer"))
+            .collect();
+        assert_eq!(outers.len(), 2);
+        assert_ne!(outers[0].args, outers[1].args);
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.added.len(), 2, "{diff:?}");
+        assert_eq!(diff.added_edges.len(), 1, "{diff:?}");
+        assert_eq!(diff.added_dependencies.len(), 1, "{diff:?}");
+        assert!(diff.removed.is_empty(), "{diff:?}");
+        assert!(diff.removed_edges.is_empty(), "{diff:?}");
+        assert!(diff.removed_dependencies.is_empty(), "{diff:?}");
+
+        let reverse = after.diff(&before);
+        assert_eq!(reverse.removed, diff.added);
+        assert_eq!(reverse.removed_edges, diff.added_edges);
+        assert_eq!(reverse.removed_dependencies, diff.added_dependencies);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_diff_reexecuted() {
+    run_with_tt(|tt| async move {
+        assert_eq!(*outer(4).strongly_consistent().await.unwrap(), 9);
+        let before = tt.backend().task_graph();
+        let old = before
+            .tasks
+            .iter()
+            .find(|node| node.name.ends_with("inner"))
+            .expect("inner task should be in the graph");
+
+        // the task is executed again, but ends up in the same state with the
+        // same output
+        let backend = tt.backend();
+        let function = backend.try_get_function_id(old.id).unwrap();
+        assert_eq!(backend.invalidate_function(function, &*tt), 1);
+        assert_eq!(*inner(4).strongly_consistent().await.unwrap(), 8);
+        let after = tt.backend().task_graph();
+        let new = after.tasks.iter().find(|node| node.id == old.id).unwrap();
+        assert_eq!(new.state, old.state);
+        assert_eq!(new.output, old.output);
+        assert_ne!(new.execution, old.execution);
+
+        let diff = before.diff(&after);
+        assert!(diff.changed.contains(&old.id), "{diff:?}");
+        assert!(diff.added.is_empty(), "{diff:?}");
+        assert!(diff.removed.is_empty(), "{diff:?}");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_root_tasks() {
+    run_with_tt(|tt| async move {
+        let root = tt.spawn_root_task(|| async { Ok(outer(6)) });
+        tt.wait_task_completion(root, ReadConsistency::Strong)
+            .await
+            .unwrap();
+        assert_eq!(tt.backend().root_tasks(), vec![root]);
+
+        let graph = tt.backend().task_graph();
+        let root_node = graph
+            .tasks
+            .iter()
+            .find(|node| node.id == root)
+            .expect("root task should be in the graph");
+        let outer = graph
+            .tasks
+            .iter()
+            .find(|node| node.name.ends_with("outer"))
+            .expect("outer task should be in the graph");
+        assert!(root_node.children.contains(&outer.id));
+
+        tt.dispose_root_task(root);
+        assert!(tt.backend().root_tasks().is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_invalidate_function() {
+    run_with_tt(|tt| async move {
+        assert_eq!(*counted(1).strongly_consistent().await.unwrap(), 2);
+        assert_eq!(*counted(2).strongly_consistent().await.unwrap(), 4);
+        assert_eq!(COUNTED_EXECUTIONS.load(Ordering::SeqCst), 2);
+
+        let backend = tt.backend();
+        let task = backend
+            .task_graph()
+            .tasks
+            .into_iter()
+            .find(|node| node.name.ends_with("counted"))
+            .expect("counted task should be cached");
+        let function = backend.try_get_function_id(task.id).unwrap();
+        assert_eq!(backend.invalidate_function(function, &*tt), 2);
+
+        // cached tasks are reused, but have to be executed again
+        assert_eq!(*counted(1).strongly_consistent().await.unwrap(), 2);
+        assert_eq!(*counted(2).strongly_consistent().await.unwrap(), 4);
+        assert_eq!(COUNTED_EXECUTIONS.load(Ordering::SeqCst), 4);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_slowest_tasks() {
+    run_with_tt(|tt| async move {
+        assert!(tt.backend().slowest_tasks(10).is_empty());
+
+        assert_eq!(*fast(1).await.unwrap(), 1);
+        assert_eq!(*slow(2).await.unwrap(), 2);
+
+        let slowest = tt.backend().slowest_tasks(10);
+        assert_eq!(slowest.len(), 2, "{slowest:?}");
+        let (_, name, duration) = &slowest[0];
+        assert!(name.ends_with("slow"), "{slowest:?}");
+        assert!(*duration >= Duration::from_millis(50), "{slowest:?}");
+        assert!(slowest[1].1.ends_with("fast"), "{slowest:?}");
+
+        let slowest = tt.backend().slowest_tasks(1);
+        assert_eq!(slowest.len(), 1, "{slowest:?}");
+        assert!(slowest[0].1.ends_with("slow"), "{slowest:?}");
+    })
+    .await;
+}
+
+#[turbo_tasks::function]
+async fn outer(val: u64) -> Result<Vc<u64>> {
+    Ok(Vc::cell(*inner(val).await? + 1))
+}
+
+#[turbo_tasks::function]
+fn inner(val: u64) -> Vc<u64> {
+    Vc::cell(val * 2)
+}
+
+#[turbo_tasks::function]
+fn counted(val: u32) -> Result<Vc<u32>> {
+    COUNTED_EXECUTIONS.fetch_add(1, Ordering::SeqCst);
+    Ok(Vc::cell(val * 2))
+}
+
+#[turbo_tasks::function]
+fn fast(val: u32) -> Vc<u32> {
+    Vc::cell(val)
+}
+
+#[turbo_tasks::function]
+fn slow(val: u32) -> Vc<u32> {
+    // Only the time spent polling the task is measured, so await-ing a timer
+    // wouldn't count
+    std::thread::sleep(Duration::from_millis(50));
+    Vc::cell(val)
+}
+
+async fn run_with_tt<Fut>(func: impl FnOnce(Arc<TurboTasks<MemoryBackend>>) -> Fut)
+where
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    REGISTRATION.ensure_registered();
+    let tt = TurboTasks::new(MemoryBackend::default());
+    let fut = func(Arc::clone(&tt));
+    tt.run_once(async move {
+        fut.await;
+        Ok(())
+    })
+    .await
+    .unwrap();
+}
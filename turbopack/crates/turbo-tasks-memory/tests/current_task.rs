@@ -0,0 +1 @@
+../../turbo-tasks-testing/tests/current_task.rs
\ No newline at end of file
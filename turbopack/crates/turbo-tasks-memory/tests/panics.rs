@@ -0,0 +1 @@
+../../turbo-tasks-testing/tests/panics.rs
\ No newline at end of file
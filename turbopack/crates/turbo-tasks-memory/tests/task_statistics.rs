@@ -34,6 +34,7 @@ async fn test_simple_task() {
                 "turbo-tasks-memory::::double": {
                     "cache_miss": 10,
                     "cache_hit": 15,
+                    "executions": 10,
                 },
             })
         );
@@ -54,6 +55,7 @@ async fn test_await_same_vc_multiple_times() {
                 "turbo-tasks-memory::::double": {
                     "cache_miss": 1,
                     "cache_hit": 0,
+                    "executions": 1,
                 },
             })
         );
@@ -80,10 +82,12 @@ async fn test_vc_receiving_task() {
                 "turbo-tasks-memory::::double": {
                     "cache_miss": 10,
                     "cache_hit": 5,
+                    "executions": 10,
                 },
                 "turbo-tasks-memory::::double_vc": {
                     "cache_miss": 10,
                     "cache_hit": 15,
+                    "executions": 10,
                 },
             })
         );
@@ -111,14 +115,17 @@ async fn test_trait_methods() {
                 "turbo-tasks-memory::::wrap": {
                     "cache_miss": 10,
                     "cache_hit": 5,
+                    "executions": 10,
                 },
                 "turbo-tasks-memory::::WrappedU64::Doublable::double": {
                     "cache_miss": 10,
                     "cache_hit": 15,
+                    "executions": 10,
                 },
                 "turbo-tasks-memory::::WrappedU64::Doublable::double_vc": {
                     "cache_miss": 10,
                     "cache_hit": 15,
+                    "executions": 10,
                 },
             })
         );
@@ -152,14 +159,17 @@ async fn test_dyn_trait_methods() {
                 "turbo-tasks-memory::::wrap": {
                     "cache_miss": 10,
                     "cache_hit": 7,
+                    "executions": 10,
                 },
                 "turbo-tasks-memory::::WrappedU64::Doublable::double": {
                     "cache_miss": 10,
                     "cache_hit": 17,
+                    "executions": 10,
                 },
                 "turbo-tasks-memory::::WrappedU64::Doublable::double_vc": {
                     "cache_miss": 10,
                     "cache_hit": 17,
+                    "executions": 10,
                 },
             })
         );
@@ -167,6 +177,27 @@ async fn test_dyn_trait_methods() {
     .await;
 }
 
+#[tokio::test]
+async fn test_by_duration() {
+    run_with_tt(|tt| async move {
+        for i in 0..10 {
+            double_vc(double(i)).await.unwrap();
+        }
+        let stats = tt.backend().task_statistics().get().unwrap().by_duration();
+        assert_eq!(stats.len(), 2);
+        assert!(stats[0].1.duration >= stats[1].1.duration);
+        for (name, stats) in stats {
+            assert!(
+                name.ends_with("::double") || name.ends_with("::double_vc"),
+                "{name}"
+            );
+            assert_eq!(stats.executions, 10);
+            assert_eq!(stats.cache_miss, 10);
+        }
+    })
+    .await;
+}
+
 // creates Vcs, but doesn't ever execute them
 #[tokio::test]
 async fn test_no_execution() {
@@ -179,6 +210,7 @@ async fn test_no_execution() {
                 "turbo-tasks-memory::::double": {
                     "cache_miss": 1,
                     "cache_hit": 0,
+                    "executions": 0,
                 },
             })
         );
@@ -254,7 +286,9 @@ where
 }
 
 fn stats_json(tt: &TurboTasks<MemoryBackend>) -> serde_json::Value {
-    remove_hashes(serde_json::to_value(tt.backend().task_statistics().get()).unwrap())
+    remove_durations(remove_hashes(
+        serde_json::to_value(tt.backend().task_statistics().get()).unwrap(),
+    ))
 }
 
 // Global task identifiers can contain a hash of the crate and dependencies.
@@ -272,3 +306,15 @@ fn remove_hashes(mut json: serde_json::Value) -> serde_json::Value {
     };
     json
 }
+
+// Execution durations depend on timing, so they can't be compared in tests.
+fn remove_durations(mut json: serde_json::Value) -> serde_json::Value {
+    for stats in json.as_object_mut().expect("expected object").values_mut() {
+        stats
+            .as_object_mut()
+            .expect("expected object")
+            .remove("duration_us")
+            .expect("expected duration_us");
+    }
+    json
+}
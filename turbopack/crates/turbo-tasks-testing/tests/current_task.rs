@@ -0,0 +1,41 @@
+#![feature(arbitrary_self_types)]
+#![feature(arbitrary_self_types_pointers)]
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+use anyhow::Result;
+use turbo_tasks::{try_current_task_id, try_turbo_tasks, Vc};
+use turbo_tasks_testing::{register, run, Registration};
+
+static REGISTRATION: Registration = register!();
+
+#[tokio::test]
+async fn current_task_id() {
+    assert_eq!(try_current_task_id(), None);
+    run(&REGISTRATION, || async {
+        let outer = try_current_task_id().expect("should run inside of a task");
+        let inner = *own_task_id().await?;
+        assert_ne!(*outer, inner);
+        // the same call is cached and reports the same task
+        assert_eq!(*own_task_id().await?, inner);
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn turbo_tasks_context() {
+    assert!(try_turbo_tasks().is_none());
+    run(&REGISTRATION, || async {
+        assert!(try_turbo_tasks().is_some());
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[turbo_tasks::function]
+fn own_task_id() -> Result<Vc<u32>> {
+    let id = try_current_task_id().expect("should run inside of a task");
+    Ok(Vc::cell(*id))
+}
@@ -0,0 +1,106 @@
+#![feature(arbitrary_self_types)]
+#![feature(arbitrary_self_types_pointers)]
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+use anyhow::Result;
+use tokio::time::{timeout, Duration};
+use turbo_tasks::Vc;
+use turbo_tasks_testing::{register, run, Registration};
+
+static REGISTRATION: Registration = register!();
+
+#[tokio::test]
+async fn static_str_panic() {
+    run(&REGISTRATION, || async {
+        // timeout: a panicking task must not leave its readers hanging
+        let err = timeout(
+            Duration::from_secs(5),
+            panics_with_static_str().strongly_consistent(),
+        )
+        .await?
+        .err()
+        .expect("reading a panicked task should fail");
+        let message = format!("{err:?}");
+        assert!(message.contains("static panic message"), "{message}");
+        // the panicking task is part of the message
+        assert!(message.contains("panics_with_static_str"), "{message}");
+        assert!(message.contains("TaskId"), "{message}");
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn formatted_panic() {
+    run(&REGISTRATION, || async {
+        let err = timeout(
+            Duration::from_secs(5),
+            panics_with_string(Vc::cell(42)).strongly_consistent(),
+        )
+        .await?
+        .err()
+        .expect("reading a panicked task should fail");
+        let message = format!("{err:?}");
+        assert!(message.contains("formatted panic message 42"), "{message}");
+        assert!(message.contains("panics_with_string"), "{message}");
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn panic_propagates_to_caller() {
+    run(&REGISTRATION, || async {
+        let err = timeout(
+            Duration::from_secs(5),
+            calls_panicking_task().strongly_consistent(),
+        )
+        .await?
+        .err()
+        .expect("the error should propagate through the calling task");
+        let message = format!("{err:?}");
+        assert!(message.contains("static panic message"), "{message}");
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn error_names_task() {
+    run(&REGISTRATION, || async {
+        let err = fails().strongly_consistent().await.unwrap_err();
+        let message = format!("{err:?}");
+        assert!(message.contains("failure message"), "{message}");
+        assert!(message.contains("Execution of"), "{message}");
+        assert!(message.contains("fails"), "{message}");
+        assert!(message.contains("TaskId"), "{message}");
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[turbo_tasks::function]
+fn fails() -> Result<Vc<u32>> {
+    anyhow::bail!("failure message")
+}
+
+#[turbo_tasks::function]
+fn panics_with_static_str() -> Vc<u32> {
+    panic!("static panic message")
+}
+
+#[turbo_tasks::function]
+async fn panics_with_string(input: Vc<u32>) -> Result<Vc<u32>> {
+    let value = *input.await?;
+    panic!("formatted panic message {value}")
+}
+
+#[turbo_tasks::function]
+async fn calls_panicking_task() -> Result<Vc<u32>> {
+    let value = *panics_with_static_str().await?;
+    Ok(Vc::cell(value))
+}
@@ -795,13 +795,28 @@ impl<B: Backend + 'static> TurboTasks<B> {
                         ltt.close();
                         ltt.wait().await;
 
-                        let result = result.map_err(|any| match any.downcast::<String>() {
-                            Ok(owned) => Some(Cow::Owned(*owned)),
-                            Err(any) => match any.downcast::<&'static str>() {
-                                Ok(str) => Some(Cow::Borrowed(*str)),
-                                Err(_) => None,
-                            },
-                        });
+                        // Neither errors nor panics say which task they come from, so add the
+                        // task to make it possible to find the culprit
+                        let result = match result {
+                            Ok(Ok(raw_vc)) => Ok(Ok(raw_vc)),
+                            Ok(Err(err)) => {
+                                let description = this.backend.get_task_description(task_id);
+                                Ok(Err(
+                                    err.context(format!("Execution of {description} failed"))
+                                ))
+                            }
+                            Err(any) => {
+                                let message: Cow<'static, str> = match any.downcast::<String>() {
+                                    Ok(owned) => Cow::Owned(*owned),
+                                    Err(any) => match any.downcast::<&'static str>() {
+                                        Ok(str) => Cow::Borrowed(*str),
+                                        Err(_) => Cow::Borrowed("unknown panic"),
+                                    },
+                                };
+                                let description = this.backend.get_task_description(task_id);
+                                Err(Some(Cow::Owned(format!("{message} (in {description})"))))
+                            }
+                        };
                         this.backend.task_execution_result(task_id, result, &*this);
                         let stateful = this.finish_current_task_state();
                         let cell_counters = CURRENT_GLOBAL_TASK_STATE
@@ -1574,13 +1589,20 @@ impl<B: Backend + 'static> TurboTasksBackendApi<B> for TurboTasks<B> {
 }
 
 pub(crate) fn current_task(from: &str) -> TaskId {
-    match CURRENT_GLOBAL_TASK_STATE.try_with(|ts| ts.read().unwrap().task_id) {
-        Ok(id) => id,
-        Err(_) => panic!(
+    try_current_task_id().unwrap_or_else(|| {
+        panic!(
             "{} can only be used in the context of turbo_tasks task execution",
             from
-        ),
-    }
+        )
+    })
+}
+
+/// Returns the id of the task that is currently executing, or `None` when
+/// called outside of turbo_tasks task execution.
+pub fn try_current_task_id() -> Option<TaskId> {
+    CURRENT_GLOBAL_TASK_STATE
+        .try_with(|ts| ts.read().unwrap().task_id)
+        .ok()
 }
 
 pub async fn run_once<T: Send + 'static>(
@@ -1664,6 +1686,12 @@ pub fn turbo_tasks() -> Arc<dyn TurboTasksApi> {
     TURBO_TASKS.with(|arc| arc.clone())
 }
 
+/// Like [`turbo_tasks`], but returns `None` instead of panicking when called
+/// outside of a turbo_tasks context.
+pub fn try_turbo_tasks() -> Option<Arc<dyn TurboTasksApi>> {
+    TURBO_TASKS.try_with(|arc| arc.clone()).ok()
+}
+
 pub fn with_turbo_tasks<T>(func: impl FnOnce(&Arc<dyn TurboTasksApi>) -> T) -> T {
     TURBO_TASKS.with(|arc| func(arc))
 }
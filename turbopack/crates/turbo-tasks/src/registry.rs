@@ -52,12 +52,17 @@ fn register_thing<
     map_by_value: &FxDashMap<V, K>,
 ) {
     if let Entry::Vacant(e) = map_by_value.entry(value) {
+        // Global names are used to identify things across processes (e.g. in the persistent
+        // cache), so two different things must never share a name.
+        let Entry::Vacant(name_entry) = map_by_name.entry(global_name) else {
+            panic!("Duplicate registration of global name {global_name}");
+        };
         let new_id = id_factory.get();
         // SAFETY: this is a fresh id
         unsafe {
             store.insert(*new_id as usize, (value, global_name));
         }
-        map_by_name.insert(global_name, new_id);
+        name_entry.insert(new_id);
         e.insert(new_id);
     }
 }
@@ -76,6 +81,12 @@ fn get_thing_id<
     }
 }
 
+/// Registers `func` under `global_name`.
+///
+/// Panics when a different function was already registered under the same
+/// name. Note that the library and the test, example and bench targets of a
+/// crate all use the same `{crate}@{hash}::` prefix, so a function at the root
+/// of `lib.rs` and one at the root of a test file must not share a name.
 pub fn register_function(global_name: &'static str, func: &'static NativeFunction) {
     register_thing(
         global_name,
@@ -95,6 +106,10 @@ pub fn get_function_id_by_global_name(global_name: &str) -> Option<FunctionId> {
     FUNCTIONS_BY_NAME.get(global_name).map(|x| *x)
 }
 
+pub fn get_function_by_global_name(global_name: &str) -> Option<&'static NativeFunction> {
+    get_function_id_by_global_name(global_name).map(get_function)
+}
+
 pub fn get_function(id: FunctionId) -> &'static NativeFunction {
     FUNCTIONS.get(*id as usize).unwrap().0
 }
@@ -156,3 +171,45 @@ pub fn get_trait(id: TraitTypeId) -> &'static TraitType {
 pub fn get_trait_type_global_name(id: TraitTypeId) -> &'static str {
     TRAIT_TYPES.get(*id as usize).unwrap().1
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as turbo_tasks, FunctionMeta, Vc};
+
+    #[turbo_tasks::function]
+    fn registered_func() -> Vc<()> {
+        Vc::cell(())
+    }
+
+    #[test]
+    fn test_get_function_by_global_name() {
+        crate::register();
+        let id = *REGISTERED_FUNC_FUNCTION_ID;
+        let global_name = get_function_global_name(id);
+        assert!(global_name.ends_with("::registered_func"), "{global_name}");
+        assert_eq!(get_function_id_by_global_name(global_name), Some(id));
+        let func = get_function_by_global_name(global_name).unwrap();
+        assert!(std::ptr::eq(func, get_function(id)));
+        assert!(get_function_by_global_name("unknown::registered_func").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate registration of global name")]
+    fn test_duplicate_global_name() {
+        crate::register();
+        let global_name = get_function_global_name(*REGISTERED_FUNC_FUNCTION_ID);
+        // a function that is not registered yet, as all functions annotated with
+        // `#[turbo_tasks::function]` are registered by `crate::register`
+        let other: &'static NativeFunction = Box::leak(Box::new(NativeFunction::new_function(
+            "other_func".to_owned(),
+            FunctionMeta { local_cells: false },
+            other_func,
+        )));
+        register_function(global_name, other);
+    }
+
+    fn other_func() -> Vc<()> {
+        Vc::cell(())
+    }
+}